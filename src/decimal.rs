@@ -12,6 +12,27 @@ construct_uint! {
     pub struct U192(3);
 }
 
+construct_uint! {
+    pub struct U384(6);
+}
+
+/// Widen a `U192` into a `U384` so intermediate products cannot overflow
+pub(crate) fn widen(val: U192) -> U384 {
+    let mut buf = [0u8; 24];
+    val.to_big_endian(&mut buf);
+    U384::from_big_endian(&buf)
+}
+
+/// Narrow a `U384` back into a `U192`, failing if the high words are nonzero
+pub(crate) fn narrow(val: U384) -> Result<U192, DecimalError> {
+    let mut buf = [0u8; 48];
+    val.to_big_endian(&mut buf);
+    if buf[..24].iter().any(|&b| b != 0) {
+        return Err(DecimalError::MathOverflow);
+    }
+    Ok(U192::from_big_endian(&buf[24..]))
+}
+
 /// Large decimal values, precise to 18 digits
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
 pub struct Decimal(pub U192);
@@ -37,6 +58,12 @@ impl Decimal {
         U192::from(HALF_WAD)
     }
 
+    // OPTIMIZE: use const slice when fixed in BPF toolchain
+    /// `ln(2)` scaled by WAD (0.693147180559945309)
+    fn ln_two() -> U192 {
+        U192::from(693147180559945309u64)
+    }
+
     /// Create scaled decimal from percent value
     pub fn from_percent(percent: u8) -> Self {
         Self(U192::from(percent as u64 * PERCENT_SCALER))
@@ -98,7 +125,7 @@ impl Decimal {
             .checked_sub(U192::from(1u64))
             .ok_or(DecimalError::MathOverflow)?
             .checked_add(self.0)
-            .ok_or(DecimalError::MathOverflow)?
+            .ok_or(DecimalError::RoundUpOverflow)?
             .checked_div(Self::wad())
             .ok_or(DecimalError::MathOverflow)?;
         u64::try_from(ceil_val).map_err(|_| DecimalError::MathOverflow)
@@ -110,7 +137,7 @@ impl Decimal {
             .checked_sub(U192::from(1u64))
             .ok_or(DecimalError::MathOverflow)?
             .checked_add(self.0)
-            .ok_or(DecimalError::MathOverflow)?
+            .ok_or(DecimalError::RoundUpOverflow)?
             .checked_div(Self::wad())
             .ok_or(DecimalError::MathOverflow)?;
         u128::try_from(ceil_val).map_err(|_| DecimalError::MathOverflow)
@@ -132,6 +159,216 @@ impl Decimal {
             .ok_or(DecimalError::MathOverflow)?;
         u128::try_from(ceil_val).map_err(|_| DecimalError::MathOverflow)
     }
+
+    /// Square root of a scaled decimal
+    ///
+    /// Because the value is stored as `raw = value * WAD`, we have
+    /// `sqrt(raw / WAD) = sqrt(raw * WAD) / WAD`, so the result's raw
+    /// representation is `isqrt(raw * WAD)`.
+    pub fn try_sqrt(&self) -> Result<Self, DecimalError> {
+        let radicand = self
+            .0
+            .checked_mul(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        Ok(Self(Self::isqrt(radicand)))
+    }
+
+    /// Compute `self * numerator / denominator`, widening the intermediate
+    /// product into `U384` so it cannot overflow even when `self * numerator`
+    /// exceeds `U192` but the final quotient fits.
+    pub fn try_multiply_ratio(
+        self,
+        numerator: impl Into<U192>,
+        denominator: impl Into<U192>,
+    ) -> Result<Self, DecimalError> {
+        let denominator = denominator.into();
+        if denominator.is_zero() {
+            return Err(DecimalError::DivideByZero);
+        }
+        let product = widen(self.0) * widen(numerator.into());
+        let quotient = product / widen(denominator);
+        Ok(Self(narrow(quotient)?))
+    }
+
+    /// Raise to an integer power via exponentiation by squaring
+    pub fn try_pow(&self, mut exp: u64) -> Result<Self, DecimalError> {
+        let mut result = Self::one();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.try_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.try_mul(base)?;
+            }
+        }
+        Ok(result)
+    }
+
+    /// Raise to an integer power, clamping to `max` instead of erroring on overflow
+    pub fn saturating_pow(&self, mut exp: u64, max: Self) -> Self {
+        let mut result = Self::one();
+        let mut base = *self;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = match result.try_mul(base) {
+                    Ok(val) if val <= max => val,
+                    _ => return max,
+                };
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = match base.try_mul(base) {
+                    Ok(val) if val <= max => val,
+                    _ => return max,
+                };
+            }
+        }
+        result
+    }
+
+    /// Natural exponential via the Taylor series `1 + x + x²/2! + …`
+    ///
+    /// For large `x` the argument is first reduced via `exp(x) = exp(x/k)^k`
+    /// (with `k = floor(x) + 1`) so the series converges quickly.
+    pub fn try_exp(&self) -> Result<Self, DecimalError> {
+        let k = self
+            .try_floor_u64()?
+            .checked_add(1)
+            .ok_or(DecimalError::MathOverflow)?;
+        let x = self.try_div(k)?;
+        let mut term = Self::one();
+        let mut sum = Self::one();
+        let mut n = 1u64;
+        loop {
+            term = term.try_mul(x)?.try_div(n)?;
+            if term == Self::zero() {
+                break;
+            }
+            sum = sum.try_add(term)?;
+            n += 1;
+            if n > 40 {
+                break;
+            }
+        }
+        sum.try_pow(k)
+    }
+
+    /// Natural logarithm for `x >= 1`
+    ///
+    /// `x` is normalized into `[1, 2)` by extracting a power-of-two factor `m`
+    /// (so `x = 2^m · y`); `ln(y)` is then summed via the atanh series
+    /// `2·(t + t³/3 + t⁵/5 + …)` with `t = (y - 1) / (y + 1)`, and `m · ln(2)`
+    /// is added back using the WAD-scaled `ln(2)` constant.
+    ///
+    /// Because `Decimal` is unsigned it cannot represent the negative logarithm
+    /// of a sub-unit value, so `x == 0` returns [`DecimalError::DivideByZero`]
+    /// and `0 < x < 1` returns [`DecimalError::NegativeResult`].
+    pub fn try_ln(&self) -> Result<Self, DecimalError> {
+        if self.0.is_zero() {
+            return Err(DecimalError::DivideByZero);
+        }
+        if *self < Self::one() {
+            return Err(DecimalError::NegativeResult);
+        }
+        let two = Self::from(2u64);
+        let mut y = *self;
+        let mut m: u32 = 0;
+        while y >= two {
+            y = y.try_div(2u64)?;
+            m += 1;
+        }
+
+        let t = y
+            .try_sub(Self::one())?
+            .try_div(y.try_add(Self::one())?)?;
+        let tsq = t.try_mul(t)?;
+        let mut term = t;
+        let mut sum = t;
+        let mut n = 3u64;
+        loop {
+            term = term.try_mul(tsq)?;
+            let next = term.try_div(n)?;
+            if next == Self::zero() {
+                break;
+            }
+            sum = sum.try_add(next)?;
+            n += 2;
+            if n > 41 {
+                break;
+            }
+        }
+        let ln_y = sum.try_mul(2u64)?;
+
+        let m_ln_two = Self(Self::ln_two()).try_mul(m as u64)?;
+        ln_y.try_add(m_ln_two)
+    }
+
+    /// Integer square root via Newton's iteration, returning `floor(sqrt(s))`
+    fn isqrt(s: U192) -> U192 {
+        if s.is_zero() {
+            return U192::zero();
+        }
+        let mut g = U192::from(1u64) << ((s.bits() + 1) / 2);
+        loop {
+            let next = (g + s / g) >> 1;
+            if next >= g {
+                return g;
+            }
+            g = next;
+        }
+    }
+}
+
+/// A fixed-point value viewed as a rational `numerator / denominator`
+pub trait Fraction: Sized {
+    /// The raw scaled value (the numerator over WAD)
+    fn numerator(&self) -> U192;
+
+    /// The scaling denominator (WAD)
+    fn denominator(&self) -> U192;
+
+    /// Reciprocal `1 / x`, computed as `WAD * WAD / raw`
+    fn try_inv(&self) -> Result<Self, DecimalError>;
+}
+
+impl Fraction for Decimal {
+    fn numerator(&self) -> U192 {
+        self.0
+    }
+
+    fn denominator(&self) -> U192 {
+        Self::wad()
+    }
+
+    fn try_inv(&self) -> Result<Self, DecimalError> {
+        Ok(Self(
+            Self::wad()
+                .checked_mul(Self::wad())
+                .ok_or(DecimalError::MathOverflow)?
+                .checked_div(self.0)
+                .ok_or(DecimalError::DivideByZero)?,
+        ))
+    }
+}
+
+impl Fraction for Rate {
+    fn numerator(&self) -> U192 {
+        U192::from(self.to_scaled_val())
+    }
+
+    fn denominator(&self) -> U192 {
+        Decimal::wad()
+    }
+
+    fn try_inv(&self) -> Result<Self, DecimalError> {
+        let raw = self.to_scaled_val();
+        if raw == 0 {
+            return Err(DecimalError::DivideByZero);
+        }
+        Ok(Rate::from_scaled_val(WAD as u128 * WAD as u128 / raw))
+    }
 }
 
 impl fmt::Display for Decimal {
@@ -147,6 +384,43 @@ impl fmt::Display for Decimal {
     }
 }
 
+impl std::str::FromStr for Decimal {
+    type Err = DecimalError;
+
+    /// Parse the `integer.fraction` form produced by [`Display`], accepting an
+    /// optional integer part and up to `SCALE` fractional digits.
+    fn from_str(s: &str) -> Result<Self, DecimalError> {
+        let mut parts = s.split('.');
+        let int_part = parts.next().unwrap_or("");
+        let frac_part = parts.next().unwrap_or("");
+        if parts.next().is_some() {
+            return Err(DecimalError::ParseError);
+        }
+        if frac_part.len() > SCALE {
+            return Err(DecimalError::ParseError);
+        }
+        // Reject input with no digits at all (e.g. "" or ".") before padding,
+        // since padding would otherwise turn it into a spurious zero.
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(DecimalError::ParseError);
+        }
+        let mut digits = String::with_capacity(int_part.len() + SCALE);
+        digits.push_str(int_part);
+        digits.push_str(frac_part);
+        digits.push_str(&"0".repeat(SCALE - frac_part.len()));
+        let scaled = U192::from_dec_str(&digits).map_err(|_| DecimalError::ParseError)?;
+        Ok(Self(scaled))
+    }
+}
+
+impl TryFrom<&str> for Decimal {
+    type Error = DecimalError;
+
+    fn try_from(s: &str) -> Result<Self, DecimalError> {
+        s.parse()
+    }
+}
+
 impl From<u64> for Decimal {
     fn from(val: u64) -> Self {
         Self(Self::wad() * U192::from(val))
@@ -190,7 +464,7 @@ impl TryDiv<u64> for Decimal {
         Ok(Self(
             self.0
                 .checked_div(U192::from(rhs))
-                .ok_or(DecimalError::MathOverflow)?,
+                .ok_or(DecimalError::DivideByZero)?,
         ))
     }
 }
@@ -199,7 +473,7 @@ impl TryDiv<u128> for Decimal {
         Ok(Self(
             self.0
                 .checked_div(U192::from(rhs))
-                .ok_or(DecimalError::MathOverflow)?,
+                .ok_or(DecimalError::DivideByZero)?,
         ))
     }
 }
@@ -217,7 +491,7 @@ impl TryDiv<Decimal> for Decimal {
                 .checked_mul(Self::wad())
                 .ok_or(DecimalError::MathOverflow)?
                 .checked_div(rhs.0)
-                .ok_or(DecimalError::MathOverflow)?,
+                .ok_or(DecimalError::DivideByZero)?,
         ))
     }
 }
@@ -277,4 +551,153 @@ mod test {
 
         assert_eq!(pct as u128, pct_actual);
     }
+
+    #[test]
+    fn test_try_sqrt() {
+        assert_eq!(Decimal::zero().try_sqrt().unwrap(), Decimal::zero());
+        assert_eq!(Decimal::one().try_sqrt().unwrap(), Decimal::one());
+        assert_eq!(Decimal::from(4u64).try_sqrt().unwrap(), Decimal::from(2u64));
+        assert_eq!(Decimal::from(144u64).try_sqrt().unwrap(), Decimal::from(12u64));
+    }
+
+    #[test]
+    fn test_try_pow() {
+        assert_eq!(Decimal::from(5u64).try_pow(0).unwrap(), Decimal::one());
+        assert_eq!(Decimal::from(2u64).try_pow(1).unwrap(), Decimal::from(2u64));
+        assert_eq!(
+            Decimal::from(2u64).try_pow(10).unwrap(),
+            Decimal::from(1024u64)
+        );
+    }
+
+    #[test]
+    fn test_saturating_pow() {
+        let max = Decimal::from(100u64);
+        assert_eq!(
+            Decimal::from(2u64).saturating_pow(3, max),
+            Decimal::from(8u64)
+        );
+        // 2^10 == 1024 exceeds the cap and is clamped.
+        assert_eq!(Decimal::from(2u64).saturating_pow(10, max), max);
+    }
+
+    #[test]
+    fn test_try_multiply_ratio() {
+        // Simple pro-rata share.
+        assert_eq!(
+            Decimal::from(100u64)
+                .try_multiply_ratio(3u64, 4u64)
+                .unwrap(),
+            Decimal::from(75u64)
+        );
+        // The intermediate product overflows U192, but the quotient fits.
+        let big = Decimal(U192::MAX);
+        assert_eq!(big.try_multiply_ratio(2u64, 2u64).unwrap(), big);
+    }
+
+    #[test]
+    fn test_try_multiply_ratio_divide_by_zero() {
+        assert_eq!(
+            Decimal::from(1u64).try_multiply_ratio(1u64, 0u64),
+            Err(DecimalError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_try_exp_known_values() {
+        assert_eq!(Decimal::zero().try_exp().unwrap(), Decimal::one());
+        // exp(1) ≈ 2.718281828…; the truncated series lands within 1e-9.
+        let e = Decimal::one().try_exp().unwrap();
+        let expected = Decimal::from_scaled_val(2_718_281_828_459_045_235);
+        let diff = if e > expected {
+            e.try_sub(expected).unwrap()
+        } else {
+            expected.try_sub(e).unwrap()
+        };
+        assert!(diff < Decimal::from_scaled_val(1_000_000_000));
+    }
+
+    #[test]
+    fn test_try_ln_roundtrip_and_domain() {
+        assert_eq!(Decimal::one().try_ln().unwrap(), Decimal::zero());
+        assert_eq!(Decimal::zero().try_ln(), Err(DecimalError::DivideByZero));
+        // Sub-unit values have a negative logarithm an unsigned decimal can't hold.
+        assert_eq!(
+            Decimal::from_percent(50).try_ln(),
+            Err(DecimalError::NegativeResult)
+        );
+        // ln(exp(2)) ≈ 2 within tolerance.
+        let x = Decimal::from(2u64);
+        let back = x.try_exp().unwrap().try_ln().unwrap();
+        let diff = if back > x {
+            back.try_sub(x).unwrap()
+        } else {
+            x.try_sub(back).unwrap()
+        };
+        assert!(diff < Decimal::from_scaled_val(1_000_000_000_000));
+    }
+
+    #[test]
+    fn test_try_inv() {
+        assert_eq!(Decimal::one().try_inv().unwrap(), Decimal::one());
+        // 1 / 2 == 0.5
+        assert_eq!(
+            Decimal::from(2u64).try_inv().unwrap(),
+            Decimal::from_percent(50)
+        );
+        assert_eq!(Decimal::zero().try_inv(), Err(DecimalError::DivideByZero));
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        for d in [
+            Decimal::from_percent(50),
+            Decimal::from(1u64),
+            Decimal::from(123_456_789u64),
+        ] {
+            let s = d.to_string();
+            assert_eq!(s.parse::<Decimal>(), Ok(d));
+            assert_eq!(Decimal::try_from(s.as_str()), Ok(d));
+        }
+    }
+
+    #[test]
+    fn test_from_str_rejects_malformed() {
+        // More than SCALE (18) fractional digits.
+        assert_eq!(
+            "1.0000000000000000000".parse::<Decimal>(),
+            Err(DecimalError::ParseError)
+        );
+        // Multiple dots.
+        assert_eq!("1.2.3".parse::<Decimal>(), Err(DecimalError::ParseError));
+        // No digits at all.
+        assert_eq!("".parse::<Decimal>(), Err(DecimalError::ParseError));
+        assert_eq!(".".parse::<Decimal>(), Err(DecimalError::ParseError));
+        // Integer part that does not fit in U192.
+        assert_eq!(
+            "9".repeat(80).parse::<Decimal>(),
+            Err(DecimalError::ParseError)
+        );
+    }
+
+    #[test]
+    fn test_from_str_bare_dot_is_accepted() {
+        // A bare trailing or leading dot is treated as the omitted part being zero.
+        assert_eq!("1.".parse::<Decimal>(), Ok(Decimal::from(1u64)));
+        assert_eq!(".5".parse::<Decimal>(), Ok(Decimal::from_percent(50)));
+    }
+
+    #[test]
+    fn test_try_ceil_round_up_overflow() {
+        // `wad - 1 + self.0` overflows near the representable max.
+        let near_max = Decimal(U192::MAX);
+        assert_eq!(
+            near_max.try_ceil_u64(),
+            Err(DecimalError::RoundUpOverflow)
+        );
+        assert_eq!(
+            near_max.try_ceil_u128(),
+            Err(DecimalError::RoundUpOverflow)
+        );
+    }
 }
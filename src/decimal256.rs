@@ -0,0 +1,279 @@
+#![allow(clippy::assign_op_pattern)]
+#![allow(clippy::ptr_offset_with_cast)]
+
+use std::{convert::TryFrom, fmt};
+
+use crate::common::*;
+use crate::decimal::{narrow, widen, Decimal, U384};
+use crate::error::*;
+
+/// Wide decimal values, precise to 18 digits, backed by `U384`
+///
+/// Mirrors [`Decimal`] but recovers the high-end `u64`/`u128` range the U192
+/// representation sacrifices, so computations whose intermediate products
+/// exceed U192 no longer overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct Decimal256(pub U384);
+
+impl Decimal256 {
+    /// One
+    pub fn one() -> Self {
+        Self(Self::wad())
+    }
+
+    /// Zero
+    pub fn zero() -> Self {
+        Self(U384::zero())
+    }
+
+    // OPTIMIZE: use const slice when fixed in BPF toolchain
+    fn wad() -> U384 {
+        U384::from(WAD)
+    }
+
+    // OPTIMIZE: use const slice when fixed in BPF toolchain
+    fn half_wad() -> U384 {
+        U384::from(HALF_WAD)
+    }
+
+    /// Create scaled decimal from percent value
+    pub fn from_percent(percent: u8) -> Self {
+        Self(U384::from(percent as u64 * PERCENT_SCALER))
+    }
+
+    pub fn from_percent_u64(percent: u64) -> Self {
+        Self(U384::from(percent * PERCENT_SCALER))
+    }
+
+    /// Return scaled percent value if it fits within u128
+    pub fn to_percent(&self) -> Result<u128, DecimalError> {
+        u128::try_from(self.0 / PERCENT_SCALER).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Return scaled bps value if it fits within u128
+    pub fn to_bps(&self) -> Result<u128, DecimalError> {
+        u128::try_from(self.0 / BPS_SCALER).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Create scaled decimal from bps value
+    pub fn from_bps(bps: u16) -> Self {
+        Self(U384::from(bps as u64 * BPS_SCALER))
+    }
+
+    /// Return raw scaled value if it fits within u128
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_scaled_val(&self) -> Result<u128, DecimalError> {
+        u128::try_from(self.0).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Create decimal from scaled value
+    pub fn from_scaled_val(scaled_val: u128) -> Self {
+        Self(U384::from(scaled_val))
+    }
+
+    /// Round scaled decimal to u64
+    pub fn try_round_u64(&self) -> Result<u64, DecimalError> {
+        let rounded_val = Self::half_wad()
+            .checked_add(self.0)
+            .ok_or(DecimalError::MathOverflow)?
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u64::try_from(rounded_val).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Round scaled decimal to u128
+    pub fn try_round_u128(&self) -> Result<u128, DecimalError> {
+        let rounded_val = Self::half_wad()
+            .checked_add(self.0)
+            .ok_or(DecimalError::MathOverflow)?
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u128::try_from(rounded_val).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Ceiling scaled decimal to u64
+    pub fn try_ceil_u64(&self) -> Result<u64, DecimalError> {
+        let ceil_val = Self::wad()
+            .checked_sub(U384::from(1u64))
+            .ok_or(DecimalError::MathOverflow)?
+            .checked_add(self.0)
+            .ok_or(DecimalError::RoundUpOverflow)?
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u64::try_from(ceil_val).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Ceiling scaled decimal to u128
+    pub fn try_ceil_u128(&self) -> Result<u128, DecimalError> {
+        let ceil_val = Self::wad()
+            .checked_sub(U384::from(1u64))
+            .ok_or(DecimalError::MathOverflow)?
+            .checked_add(self.0)
+            .ok_or(DecimalError::RoundUpOverflow)?
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u128::try_from(ceil_val).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    /// Floor scaled decimal to u64
+    pub fn try_floor_u64(&self) -> Result<u64, DecimalError> {
+        let floor_val = self
+            .0
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u64::try_from(floor_val).map_err(|_| DecimalError::MathOverflow)
+    }
+
+    pub fn try_floor_u128(&self) -> Result<u128, DecimalError> {
+        let floor_val = self
+            .0
+            .checked_div(Self::wad())
+            .ok_or(DecimalError::MathOverflow)?;
+        u128::try_from(floor_val).map_err(|_| DecimalError::MathOverflow)
+    }
+}
+
+impl fmt::Display for Decimal256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut scaled_val = self.0.to_string();
+        if scaled_val.len() <= SCALE {
+            scaled_val.insert_str(0, &vec!["0"; SCALE - scaled_val.len()].join(""));
+            scaled_val.insert_str(0, "0.");
+        } else {
+            scaled_val.insert(scaled_val.len() - SCALE, '.');
+        }
+        f.write_str(&scaled_val)
+    }
+}
+
+impl From<u64> for Decimal256 {
+    fn from(val: u64) -> Self {
+        Self(Self::wad() * U384::from(val))
+    }
+}
+
+impl From<u128> for Decimal256 {
+    fn from(val: u128) -> Self {
+        Self(Self::wad() * U384::from(val))
+    }
+}
+
+impl From<Decimal> for Decimal256 {
+    /// Lossless widening: both types scale by the same WAD.
+    fn from(val: Decimal) -> Self {
+        Self(widen(val.0))
+    }
+}
+
+impl TryFrom<Decimal256> for Decimal {
+    type Error = DecimalError;
+
+    /// Checked narrowing: errors if the value does not fit in U192.
+    fn try_from(val: Decimal256) -> Result<Self, DecimalError> {
+        Ok(Self(narrow(val.0)?))
+    }
+}
+
+impl TryAdd for Decimal256 {
+    fn try_add(self, rhs: Self) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_add(rhs.0)
+                .ok_or(DecimalError::MathOverflow)?,
+        ))
+    }
+}
+
+impl TrySub for Decimal256 {
+    fn try_sub(self, rhs: Self) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_sub(rhs.0)
+                .ok_or(DecimalError::MathOverflow)?,
+        ))
+    }
+}
+
+impl TryDiv<u64> for Decimal256 {
+    fn try_div(self, rhs: u64) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_div(U384::from(rhs))
+                .ok_or(DecimalError::DivideByZero)?,
+        ))
+    }
+}
+
+impl TryDiv<u128> for Decimal256 {
+    fn try_div(self, rhs: u128) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_div(U384::from(rhs))
+                .ok_or(DecimalError::DivideByZero)?,
+        ))
+    }
+}
+
+impl TryDiv<Decimal256> for Decimal256 {
+    fn try_div(self, rhs: Self) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_mul(Self::wad())
+                .ok_or(DecimalError::MathOverflow)?
+                .checked_div(rhs.0)
+                .ok_or(DecimalError::DivideByZero)?,
+        ))
+    }
+}
+
+impl TryMul<u64> for Decimal256 {
+    fn try_mul(self, rhs: u64) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_mul(U384::from(rhs))
+                .ok_or(DecimalError::MathOverflow)?,
+        ))
+    }
+}
+
+impl TryMul<u128> for Decimal256 {
+    fn try_mul(self, rhs: u128) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_mul(U384::from(rhs))
+                .ok_or(DecimalError::MathOverflow)?,
+        ))
+    }
+}
+
+impl TryMul<Decimal256> for Decimal256 {
+    fn try_mul(self, rhs: Self) -> Result<Self, DecimalError> {
+        Ok(Self(
+            self.0
+                .checked_mul(rhs.0)
+                .ok_or(DecimalError::MathOverflow)?
+                .checked_div(Self::wad())
+                .ok_or(DecimalError::MathOverflow)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn test_widen_narrow_roundtrip() {
+        let d = Decimal::from(12_345u64);
+        let wide = Decimal256::from(d);
+        assert_eq!(Decimal::try_from(wide).unwrap(), d);
+    }
+
+    #[test]
+    fn test_narrow_overflow_errors() {
+        // A value whose high words are set cannot fit back into U192.
+        let wide = Decimal256(U384::MAX);
+        assert_eq!(Decimal::try_from(wide), Err(DecimalError::MathOverflow));
+    }
+}
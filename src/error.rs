@@ -0,0 +1,33 @@
+//! Errors surfaced by fixed-point decimal arithmetic
+
+use std::fmt;
+
+/// Errors that can occur while operating on [`Decimal`](crate::decimal::Decimal)
+/// and [`Rate`](crate::rate::Rate) values.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DecimalError {
+    /// An arithmetic operation overflowed the fixed-point representation.
+    MathOverflow,
+    /// A divisor was zero.
+    DivideByZero,
+    /// Rounding up carried the value past the representable maximum.
+    RoundUpOverflow,
+    /// A string could not be parsed into a decimal value.
+    ParseError,
+    /// The result would be negative, which an unsigned decimal cannot represent.
+    NegativeResult,
+}
+
+impl fmt::Display for DecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecimalError::MathOverflow => f.write_str("math operation overflowed"),
+            DecimalError::DivideByZero => f.write_str("division by zero"),
+            DecimalError::RoundUpOverflow => f.write_str("rounding up overflowed"),
+            DecimalError::ParseError => f.write_str("could not parse decimal"),
+            DecimalError::NegativeResult => f.write_str("result would be negative"),
+        }
+    }
+}
+
+impl std::error::Error for DecimalError {}